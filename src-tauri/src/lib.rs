@@ -6,13 +6,20 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::process::{Child, Command, Stdio};
-use std::sync::{Arc, Mutex};
+use std::sync::Mutex;
 use tauri::{AppHandle, Emitter, Manager, State};
 
-// Global state for running processes
+// Global state for running processes. Each entry maps a process key
+// ("tab_id:script_name") to the pgid of the process group it spawned into, so
+// stop_script can signal the whole tree (shell -> npm -> node/next server)
+// instead of just the shell. The Child itself lives with the monitor thread
+// that blocks on child.wait() - see run_script.
 pub struct AppState {
-    processes: Mutex<HashMap<String, Child>>,
+    processes: Mutex<HashMap<String, u32>>,
     last_project_path: Mutex<Option<String>>,
+    // Live plugin child processes, keyed by plugin name, so stop_plugin can
+    // reach a plugin mid-run.
+    plugins: Mutex<HashMap<String, Child>>,
 }
 
 impl Default for AppState {
@@ -20,22 +27,57 @@ impl Default for AppState {
         Self {
             processes: Mutex::new(HashMap::new()),
             last_project_path: Mutex::new(None),
+            plugins: Mutex::new(HashMap::new()),
         }
     }
 }
 
+// A single dependency entry: the declared semver range from package.json,
+// the version actually pinned in the lockfile (if resolved yet), where that
+// version was fetched from, and an `outdated` flag. load_project always sets
+// `outdated` to false; check_outdated reports newer versions separately (its
+// return value and `package-outdated` events) and does not update this field.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DependencyInfo {
+    range: String,
+    resolved: Option<String>,
+    registry: Option<String>,
+    outdated: bool,
+}
+
+// A lockfile-resolved package: the pinned version and the registry it was
+// fetched from (e.g. "https://registry.npmjs.org"), when the lockfile
+// records a download URL to derive that from.
+#[derive(Clone)]
+struct ResolvedPackage {
+    version: String,
+    registry: Option<String>,
+}
+
+// Pull the scheme+host out of a lockfile tarball URL, e.g.
+// "https://registry.npmjs.org/lodash/-/lodash-4.17.21.tgz" ->
+// "https://registry.npmjs.org". Used for both package-lock.json's "resolved"
+// field and yarn.lock's `resolved "..."` line.
+fn extract_registry(url: &str) -> Option<String> {
+    let scheme_end = url.find("://")? + 3;
+    let host_end = url[scheme_end..].find('/').map(|i| scheme_end + i).unwrap_or(url.len());
+    Some(url[..host_end].to_string())
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct ProjectInfo {
     name: String,
     version: String,
     scripts: HashMap<String, String>,
-    dependencies: HashMap<String, String>,
+    dependencies: HashMap<String, DependencyInfo>,
     #[serde(rename = "devDependencies")]
-    dev_dependencies: HashMap<String, String>,
+    dev_dependencies: HashMap<String, DependencyInfo>,
     #[serde(rename = "nodeModulesInstalled")]
     node_modules_installed: bool,
     #[serde(rename = "projectPath")]
     project_path: String,
+    #[serde(rename = "packageManager")]
+    package_manager: String,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -66,12 +108,300 @@ pub struct PortInfo {
     process_name: String,
 }
 
+#[derive(Serialize, Clone)]
+pub struct ScriptRestart {
+    script: String,
+    tab_id: String,
+    path: String,
+}
+
+// Figure out which package manager a project uses, the same way `load_project`
+// figures out what's inside package.json: look at what's actually on disk first.
+fn detect_package_manager(project_path: &PathBuf) -> String {
+    if project_path.join("yarn.lock").exists() {
+        return "yarn".to_string();
+    }
+    if project_path.join("pnpm-lock.yaml").exists() {
+        return "pnpm".to_string();
+    }
+    if project_path.join("bun.lockb").exists() {
+        return "bun".to_string();
+    }
+    if project_path.join("package-lock.json").exists() {
+        return "npm".to_string();
+    }
+
+    // No lockfile present yet (fresh clone) - fall back to the "packageManager"
+    // field in package.json, e.g. "pnpm@8.6.0".
+    let pkg_path = project_path.join("package.json");
+    if let Ok(content) = fs::read_to_string(&pkg_path) {
+        if let Ok(pkg) = serde_json::from_str::<serde_json::Value>(&content) {
+            if let Some(pm) = pkg.get("packageManager").and_then(|v| v.as_str()) {
+                if let Some(name) = pm.split('@').next() {
+                    if !name.is_empty() {
+                        return name.to_string();
+                    }
+                }
+            }
+        }
+    }
+
+    "npm".to_string()
+}
+
+// Read the resolved (actually-installed) version of every package named in
+// the project's lockfile, keyed by package name. Analogous to how tauri-cli's
+// CargoLock/CargoLockPackage turn Cargo.lock into a name -> version map.
+fn read_resolved_versions(project_path: &PathBuf, manager: &str) -> HashMap<String, ResolvedPackage> {
+    let (lockfile_name, parser): (&str, fn(&str) -> HashMap<String, ResolvedPackage>) = match manager {
+        "yarn" => ("yarn.lock", parse_yarn_lock),
+        "pnpm" => ("pnpm-lock.yaml", parse_pnpm_lock),
+        // bun.lockb is a binary format; no resolved-version parsing for it yet.
+        "bun" => return HashMap::new(),
+        _ => ("package-lock.json", parse_package_lock),
+    };
+
+    match fs::read_to_string(project_path.join(lockfile_name)) {
+        Ok(content) => parser(&content),
+        Err(_) => HashMap::new(),
+    }
+}
+
+// package-lock.json v2/v3: a flat "packages" map keyed by
+// "node_modules/<name>" (or "node_modules/a/node_modules/<name>" when nested).
+fn parse_package_lock(content: &str) -> HashMap<String, ResolvedPackage> {
+    let mut resolved = HashMap::new();
+
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(content) else {
+        return resolved;
+    };
+
+    if let Some(packages) = json.get("packages").and_then(|v| v.as_object()) {
+        for (key, entry) in packages {
+            if key.is_empty() {
+                continue; // the root project entry
+            }
+            let Some(name) = key.rsplit("node_modules/").next() else {
+                continue;
+            };
+            if let Some(version) = entry.get("version").and_then(|v| v.as_str()) {
+                let registry = entry.get("resolved").and_then(|v| v.as_str()).and_then(extract_registry);
+                resolved.insert(name.to_string(), ResolvedPackage { version: version.to_string(), registry });
+            }
+        }
+    }
+
+    resolved
+}
+
+// yarn.lock: blocks of comma-separated specs ("lodash@^4.17.21, lodash@^4.17.4:")
+// followed by indented `version "..."` lines.
+fn parse_yarn_lock(content: &str) -> HashMap<String, ResolvedPackage> {
+    let mut resolved = HashMap::new();
+    let mut pending_names: Vec<String> = Vec::new();
+    let mut pending_version: Option<String> = None;
+    let mut pending_registry: Option<String> = None;
+
+    let mut flush = |names: &mut Vec<String>, version: &mut Option<String>, registry: &mut Option<String>, resolved: &mut HashMap<String, ResolvedPackage>| {
+        if let Some(version) = version.take() {
+            for name in names.drain(..) {
+                resolved.insert(name, ResolvedPackage { version: version.clone(), registry: registry.clone() });
+            }
+        } else {
+            names.clear();
+        }
+        *registry = None;
+    };
+
+    for line in content.lines() {
+        if let Some(header) = line.strip_suffix(':') {
+            if !line.starts_with(' ') && !line.starts_with('#') {
+                flush(&mut pending_names, &mut pending_version, &mut pending_registry, &mut resolved);
+                pending_names = header
+                    .split(", ")
+                    .filter_map(|spec| spec.trim_matches('"').rsplit_once('@'))
+                    .map(|(name, _range)| name.to_string())
+                    .collect();
+                continue;
+            }
+        }
+
+        let trimmed = line.trim_start();
+        if !pending_names.is_empty() && trimmed.starts_with("version ") {
+            pending_version = trimmed.split('"').nth(1).map(|s| s.to_string());
+        }
+        if !pending_names.is_empty() && trimmed.starts_with("resolved ") {
+            pending_registry = trimmed.split('"').nth(1).and_then(extract_registry);
+        }
+    }
+    flush(&mut pending_names, &mut pending_version, &mut pending_registry, &mut resolved);
+
+    resolved
+}
+
+// pnpm-lock.yaml: under a top-level `packages:` key, entries like
+// `  /lodash@4.17.21:` (older format) or `  lodash@4.17.21:` (newer format).
+// Parsed with plain line scanning rather than a YAML parser, since the keys
+// we need are just indented scalar mapping keys.
+// pnpm-lock.yaml doesn't record a per-package download URL in the packages
+// section (just name@version keys), so registry is always None here.
+fn parse_pnpm_lock(content: &str) -> HashMap<String, ResolvedPackage> {
+    let mut resolved = HashMap::new();
+    let mut in_packages = false;
+
+    for line in content.lines() {
+        if line == "packages:" {
+            in_packages = true;
+            continue;
+        }
+        if !in_packages {
+            continue;
+        }
+        if line.trim().is_empty() {
+            continue; // pnpm separates the key and its entries with blank lines
+        }
+        if !line.starts_with(' ') {
+            break; // left the packages section
+        }
+
+        let trimmed = line.trim_start();
+        let Some(key) = trimmed.strip_suffix(':') else {
+            continue;
+        };
+        let key = key.trim_matches('\'').trim_matches('"').trim_start_matches('/');
+        if let Some((name, version)) = key.rsplit_once('@') {
+            if !name.is_empty() {
+                resolved.insert(name.to_string(), ResolvedPackage { version: version.to_string(), registry: None });
+            }
+        }
+    }
+
+    resolved
+}
+
+// Build the (program, args) for running a script with the detected manager.
+fn script_argv(manager: &str, script_name: &str) -> (String, Vec<String>) {
+    match manager {
+        "yarn" => ("yarn".to_string(), vec![script_name.to_string()]),
+        "pnpm" => ("pnpm".to_string(), vec!["run".to_string(), script_name.to_string()]),
+        "bun" => ("bun".to_string(), vec!["run".to_string(), script_name.to_string()]),
+        _ => ("npm".to_string(), vec!["run".to_string(), script_name.to_string()]),
+    }
+}
+
+// Build the (program, args) for installing dependencies with the detected manager.
+fn install_argv(manager: &str) -> (String, Vec<String>) {
+    match manager {
+        "yarn" => ("yarn".to_string(), vec!["install".to_string()]),
+        "pnpm" => ("pnpm".to_string(), vec!["install".to_string()]),
+        "bun" => ("bun".to_string(), vec!["install".to_string()]),
+        _ => ("npm".to_string(), vec!["install".to_string()]),
+    }
+}
+
+// CREATE_NEW_PROCESS_GROUP, see:
+// https://learn.microsoft.com/en-us/windows/win32/procthread/process-creation-flags
+#[cfg(target_os = "windows")]
+const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+
+// Spawn `program args...` in `project_path`, the right way for the current OS:
+// on Windows via `cmd /C` against the manager's `.cmd` shim (same trick boltzmann
+// uses to tell `npm` from `npm.cmd`), everywhere else via the user's login shell
+// so PATH and shell config (nvm, asdf, etc.) are picked up.
+#[cfg(target_os = "windows")]
+fn manager_command(program: &str, args: &[String], project_path: &str) -> Command {
+    use std::os::windows::process::CommandExt;
+
+    let mut cmd = Command::new("cmd");
+    // npm/yarn/pnpm ship as <name>.cmd shims on Windows, but bun ships a
+    // plain bun.exe - there is no bun.cmd, so running it through the shim
+    // path would fail.
+    let shim = if program == "bun" { program.to_string() } else { format!("{}.cmd", program) };
+    let mut full_args = vec!["/C".to_string(), shim];
+    full_args.extend(args.iter().cloned());
+    cmd.args(full_args);
+    cmd.current_dir(project_path);
+    // Puts the process in its own group so stop_script can signal the whole tree
+    // without taking down the app itself.
+    cmd.creation_flags(CREATE_NEW_PROCESS_GROUP);
+    cmd
+}
+
+#[cfg(not(target_os = "windows"))]
+fn manager_command(program: &str, args: &[String], project_path: &str) -> Command {
+    use std::os::unix::process::CommandExt;
+
+    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/zsh".to_string());
+    let mut command_str = program.to_string();
+    for arg in args {
+        command_str.push(' ');
+        command_str.push_str(arg);
+    }
+    let mut cmd = Command::new(shell);
+    cmd.args(["-lc", &command_str]);
+    cmd.current_dir(project_path);
+    // process_group(0) makes the child its own group leader (pgid == its pid),
+    // so killing the group reaches grandchildren (e.g. the node/next server
+    // forked by `npm run dev`), not just the shell.
+    cmd.process_group(0);
+    cmd
+}
+
+// Send SIGTERM to a whole process group, then SIGKILL anything still alive
+// after a short grace period. Shells out to `kill`, matching how this module
+// already handles port cleanup rather than pulling in a signals crate.
+#[cfg(not(target_os = "windows"))]
+fn kill_process_group(pgid: u32) {
+    let target = format!("-{}", pgid);
+    let _ = Command::new("kill").args(["-TERM", &target]).output();
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    let _ = Command::new("kill").args(["-KILL", &target]).output();
+}
+
+#[cfg(target_os = "windows")]
+fn kill_process_group(pgid: u32) {
+    // taskkill /T walks the whole process tree rooted at the pid.
+    let _ = Command::new("taskkill")
+        .args(["/PID", &pgid.to_string(), "/T", "/F"])
+        .output();
+}
+
 // Get settings path
 fn get_settings_path() -> PathBuf {
     let config_dir = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
     config_dir.join("npm-commander").join("settings.json")
 }
 
+// Plugin manifests live next to settings.json, one JSON file per plugin.
+fn get_plugins_dir() -> PathBuf {
+    let config_dir = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    config_dir.join("npm-commander").join("plugins")
+}
+
+// A registered plugin: the executable to spawn and the args to pass it.
+// Declared commands are discovered at runtime via the `config` handshake,
+// not stored in the manifest.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PluginManifest {
+    name: String,
+    command: String,
+    #[serde(default)]
+    args: Vec<String>,
+}
+
+fn discover_plugin_manifests() -> Vec<PluginManifest> {
+    let Ok(entries) = fs::read_dir(get_plugins_dir()) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().and_then(|ext| ext.to_str()) == Some("json"))
+        .filter_map(|e| fs::read_to_string(e.path()).ok())
+        .filter_map(|content| serde_json::from_str::<PluginManifest>(&content).ok())
+        .collect()
+}
+
 // Load last used project path
 fn load_last_path() -> Option<String> {
     let path = get_settings_path();
@@ -183,15 +513,30 @@ fn load_project(path: String, state: State<AppState>) -> Result<ProjectInfo, Pro
         .unwrap_or_default();
     
     let node_modules_installed = project_path.join("node_modules").exists();
-    
+    let package_manager = detect_package_manager(&project_path);
+
+    let resolved_versions = read_resolved_versions(&project_path, &package_manager);
+    let to_dependency_map = |ranges: HashMap<String, String>| -> HashMap<String, DependencyInfo> {
+        ranges
+            .into_iter()
+            .map(|(name, range)| {
+                let pinned = resolved_versions.get(&name);
+                let resolved = pinned.map(|p| p.version.clone());
+                let registry = pinned.and_then(|p| p.registry.clone());
+                (name, DependencyInfo { range, resolved, registry, outdated: false })
+            })
+            .collect()
+    };
+
     Ok(ProjectInfo {
         name,
         version,
         scripts,
-        dependencies,
-        dev_dependencies,
+        dependencies: to_dependency_map(dependencies),
+        dev_dependencies: to_dependency_map(dev_dependencies),
         node_modules_installed,
         project_path: path,
+        package_manager,
     })
 }
 
@@ -260,50 +605,39 @@ async fn kill_all_ports() -> Result<String, String> {
     Ok("Cleanup command sent for all ports".to_string())
 }
 
-#[tauri::command]
-async fn run_script(
-    app: AppHandle,
-    project_path: String,
-    script_name: String,
-    tab_id: String,
-    state: State<'_, AppState>,
-) -> Result<bool, String> {
+// Spawn `script_name` via the project's package manager, wire up output
+// streaming and exit tracking, and register its pgid in AppState.processes.
+// Shared by run_script and the watch-mode restart path so both go through the
+// exact same process bookkeeping.
+fn spawn_and_track(
+    app: &AppHandle,
+    project_path: &str,
+    script_name: &str,
+    tab_id: &str,
+) -> Result<(), String> {
     let process_key = format!("{}:{}", tab_id, script_name);
 
-    // Check if already running
-    {
-        let processes = state.processes.lock().map_err(|e| e.to_string())?;
-        if processes.contains_key(&process_key) {
-            return Err(format!("Script '{}' is already running in this tab", script_name));
-        }
-    }
-    
     // Check if we need to cleanup dev environment
-    if ["dev", "start", "serve"].contains(&script_name.as_str()) {
-        cleanup_dev_environment(&project_path);
+    if ["dev", "start", "serve"].contains(&script_name) {
+        cleanup_dev_environment(project_path);
     }
 
-    // Spawn npm process using login shell to get PATH
-    let command_str = format!("npm run {}", script_name);
-    
-    let mut child = Command::new("/bin/zsh")
-        .args(["-lc", &command_str])
-        .current_dir(&project_path)
+    // Spawn the script using whichever package manager this project actually uses
+    let manager = detect_package_manager(&PathBuf::from(project_path));
+    let (program, args) = script_argv(&manager, script_name);
+
+    let mut child = manager_command(&program, &args, project_path)
         .env("FORCE_COLOR", "1")
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
         .map_err(|e| format!("Failed to start script: {}", e))?;
-    
-    let script_name_clone = script_name.clone();
-    let app_clone = app.clone();
-    let tab_id_clone = tab_id.clone();
-    
+
     // Read stdout in background
     if let Some(stdout) = child.stdout.take() {
-        let script = script_name.clone();
+        let script = script_name.to_string();
         let app = app.clone();
-        let t_id = tab_id.clone();
+        let t_id = tab_id.to_string();
         std::thread::spawn(move || {
             use std::io::{BufRead, BufReader};
             let reader = BufReader::new(stdout);
@@ -319,12 +653,12 @@ async fn run_script(
             }
         });
     }
-    
+
     // Read stderr in background
     if let Some(stderr) = child.stderr.take() {
-        let script = script_name.clone();
+        let script = script_name.to_string();
         let app = app.clone();
-        let t_id = tab_id.clone();
+        let t_id = tab_id.to_string();
         std::thread::spawn(move || {
             use std::io::{BufRead, BufReader};
             let reader = BufReader::new(stderr);
@@ -340,61 +674,249 @@ async fn run_script(
             }
         });
     }
-    
-    // Store process
-    {
+
+    // The child is its own process group leader, so its pgid is just its pid.
+    let pgid = child.id();
+
+    // Track the pgid so stop_script can find it; the Child itself moves into
+    // the monitor thread below.
+    if let Some(state) = app.try_state::<AppState>() {
         let mut processes = state.processes.lock().map_err(|e| e.to_string())?;
-        processes.insert(process_key.clone(), child);
+        processes.insert(process_key.clone(), pgid);
     }
-    
-    // Wait for process to exit in background using the app handle
+
+    // Block on the child directly and emit script-exit the instant it returns,
+    // instead of polling try_wait() on a timer.
+    let app_clone = app.clone();
     let script_for_monitor = process_key.clone();
-    let script_name_for_exit = script_name_clone.clone();
-    let tab_id_for_exit = tab_id_clone.clone();
-    
+    let script_name_for_exit = script_name.to_string();
+    let tab_id_for_exit = tab_id.to_string();
+
     std::thread::spawn(move || {
-        // Poll until process exits
-        std::thread::sleep(std::time::Duration::from_millis(100));
-        
+        let code = match child.wait() {
+            Ok(status) => status.code().unwrap_or(-1),
+            Err(_) => -1,
+        };
+
+        // Only this pgid's own entry counts as a real exit. A watch restart
+        // (or a manual stop) already removed or overwrote the map entry
+        // before this wait() returned, so a mismatch here means the entry is
+        // gone (manual stop) or now belongs to the restarted process - leave
+        // it alone and report no exit for this process.
+        if clear_if_current(&app_clone, &script_for_monitor, pgid) {
+            let _ = app_clone.emit("script-exit", ScriptExit {
+                script: script_name_for_exit,
+                code,
+                tab_id: tab_id_for_exit,
+            });
+        }
+    });
+
+    Ok(())
+}
+
+// Remove `process_key` from AppState.processes only if it still points at
+// `pgid`, returning whether it did. Used by the exit monitor to tell a real
+// exit apart from a stale thread whose process was already replaced (watch
+// restart) or removed (manual stop) by someone else.
+fn clear_if_current(app: &AppHandle, process_key: &str, pgid: u32) -> bool {
+    let Some(state) = app.try_state::<AppState>() else {
+        return false;
+    };
+    let Ok(mut processes) = state.processes.lock() else {
+        return false;
+    };
+    if processes.get(process_key) == Some(&pgid) {
+        processes.remove(process_key);
+        true
+    } else {
+        false
+    }
+}
+
+// Restart a watched script after its source files settle: kill the current
+// process group, respawn the same command, and let the frontend know why.
+fn restart_watched_script(
+    app: &AppHandle,
+    project_path: &str,
+    script_name: &str,
+    tab_id: &str,
+    changed_path: &str,
+) {
+    let process_key = format!("{}:{}", tab_id, script_name);
+
+    if let Some(state) = app.try_state::<AppState>() {
+        if let Ok(mut processes) = state.processes.lock() {
+            if let Some(pgid) = processes.remove(&process_key) {
+                kill_process_group(pgid);
+            }
+        }
+    }
+
+    if let Err(e) = spawn_and_track(app, project_path, script_name, tab_id) {
+        eprintln!("Failed to restart '{}' after file change: {}", script_name, e);
+        return;
+    }
+
+    let _ = app.emit("script-restart", ScriptRestart {
+        script: script_name.to_string(),
+        tab_id: tab_id.to_string(),
+        path: changed_path.to_string(),
+    });
+}
+
+// Paths under these directories never justify a restart.
+const WATCH_IGNORED_DIRS: [&str; 4] = ["node_modules", ".next", ".git", "dist"];
+
+fn is_watch_ignored(path: &std::path::Path) -> bool {
+    path.components().any(|c| {
+        WATCH_IGNORED_DIRS.contains(&c.as_os_str().to_string_lossy().as_ref())
+    })
+}
+
+// Register a non-recursive watch on `dir` and every subdirectory, skipping
+// WATCH_IGNORED_DIRS entirely so they (and everything inside them, e.g.
+// node_modules' own huge tree) never consume an inotify watch. A single
+// RecursiveMode::Recursive watch on the project root would register every
+// directory including the ignored ones, which is exactly what routinely
+// blows through fs.inotify.max_user_watches on real Node projects.
+fn register_watch_paths(watcher: &mut notify::RecommendedWatcher, dir: &std::path::Path) -> notify::Result<()> {
+    use notify::{RecursiveMode, Watcher};
+
+    watcher.watch(dir, RecursiveMode::NonRecursive)?;
+
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let name = entry.file_name();
+            if WATCH_IGNORED_DIRS.contains(&name.to_string_lossy().as_ref()) {
+                continue;
+            }
+            // Best-effort: a subdirectory we fail to watch (permissions, a
+            // race with deletion) shouldn't stop the rest of the tree.
+            let _ = register_watch_paths(watcher, &path);
+        }
+    }
+
+    Ok(())
+}
+
+// A NonRecursive watch per directory doesn't pick up directories created
+// after the watch started (e.g. `mkdir src/feature`), so the event loop
+// calls this on every Create event: if it's a new, non-ignored directory,
+// register a watch on it (and anything already inside it) immediately.
+fn register_new_directories(watcher: &mut notify::RecommendedWatcher, event: &notify::Event) {
+    if !matches!(event.kind, notify::EventKind::Create(_)) {
+        return;
+    }
+    for path in &event.paths {
+        if is_watch_ignored(path) {
+            continue;
+        }
+        if path.is_dir() {
+            let _ = register_watch_paths(watcher, path);
+        }
+    }
+}
+
+// Watch the project for source changes and restart the script whenever they
+// settle. Debounces over ~200ms by draining any further events that arrive
+// within the window before acting, so a save-all doesn't trigger a restart
+// per file. Stops on its own once the tab's process is no longer tracked
+// (stopped manually, or the tab was closed).
+fn spawn_watcher(app: AppHandle, project_path: String, script_name: String, tab_id: String) {
+    std::thread::spawn(move || {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(tx) {
+            Ok(w) => w,
+            Err(e) => {
+                eprintln!("Failed to start watcher for {}: {}", project_path, e);
+                return;
+            }
+        };
+
+        if let Err(e) = register_watch_paths(&mut watcher, std::path::Path::new(&project_path)) {
+            eprintln!("Failed to watch {}: {}", project_path, e);
+            return;
+        }
+
+        let process_key = format!("{}:{}", tab_id, script_name);
+        let debounce = std::time::Duration::from_millis(200);
+
         loop {
-            std::thread::sleep(std::time::Duration::from_secs(1));
-            
-            // Try to get the process state from app
-            let state = app_clone.try_state::<AppState>();
-            if let Some(state) = state {
-                if let Ok(mut processes) = state.processes.lock() {
-                    if let Some(child) = processes.get_mut(&script_for_monitor) {
-                        match child.try_wait() {
-                            Ok(Some(status)) => {
-                                let code = status.code().unwrap_or(-1);
-                                processes.remove(&script_for_monitor);
-                                let _ = app_clone.emit("script-exit", ScriptExit {
-                                    script: script_name_for_exit.clone(),
-                                    code,
-                                    tab_id: tab_id_for_exit.clone(),
-                                });
-                                break;
-                            }
-                            Ok(None) => {
-                                // Still running
-                            }
-                            Err(_) => {
-                                processes.remove(&script_for_monitor);
-                                break;
-                            }
-                        }
-                    } else {
-                        // Process was removed (stopped manually)
-                        break;
-                    }
-                }
-            } else {
-                // App state not available, exit
+            let still_running = app
+                .try_state::<AppState>()
+                .map(|state| {
+                    state.processes.lock().map(|p| p.contains_key(&process_key)).unwrap_or(false)
+                })
+                .unwrap_or(false);
+            if !still_running {
                 break;
             }
+
+            let event = match rx.recv_timeout(debounce) {
+                Ok(Ok(event)) => event,
+                Ok(Err(_)) | Err(_) => continue,
+            };
+            register_new_directories(&mut watcher, &event);
+
+            let mut changed = event.paths.into_iter().find(|p| !is_watch_ignored(p));
+            if changed.is_none() {
+                continue;
+            }
+
+            // Settle: keep draining events that arrive within the debounce
+            // window before acting on the most recent relevant one.
+            while let Ok(Ok(event)) = rx.recv_timeout(debounce) {
+                register_new_directories(&mut watcher, &event);
+                if let Some(path) = event.paths.into_iter().find(|p| !is_watch_ignored(p)) {
+                    changed = Some(path);
+                }
+            }
+
+            if let Some(path) = changed {
+                restart_watched_script(
+                    &app,
+                    &project_path,
+                    &script_name,
+                    &tab_id,
+                    &path.to_string_lossy(),
+                );
+            }
         }
     });
-    
+}
+
+#[tauri::command]
+async fn run_script(
+    app: AppHandle,
+    project_path: String,
+    script_name: String,
+    tab_id: String,
+    // Optional so existing frontend callers that predate watch mode keep
+    // working unchanged; defaults to no watching.
+    watch: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    let process_key = format!("{}:{}", tab_id, script_name);
+
+    // Check if already running
+    {
+        let processes = state.processes.lock().map_err(|e| e.to_string())?;
+        if processes.contains_key(&process_key) {
+            return Err(format!("Script '{}' is already running in this tab", script_name));
+        }
+    }
+
+    spawn_and_track(&app, &project_path, &script_name, &tab_id)?;
+
+    if watch.unwrap_or(false) {
+        spawn_watcher(app.clone(), project_path, script_name, tab_id);
+    }
+
     Ok(true)
 }
 
@@ -402,9 +924,9 @@ async fn run_script(
 fn stop_script(script_name: String, tab_id: String, state: State<AppState>) -> Result<bool, String> {
     let process_key = format!("{}:{}", tab_id, script_name);
     let mut processes = state.processes.lock().map_err(|e| e.to_string())?;
-    
-    if let Some(mut child) = processes.remove(&process_key) {
-        let _ = child.kill();
+
+    if let Some(pgid) = processes.remove(&process_key) {
+        kill_process_group(pgid);
         Ok(true)
     } else {
         Err("Script not running".to_string())
@@ -425,14 +947,15 @@ async fn install_deps(
     project_path: String,
     tab_id: String,
 ) -> Result<bool, String> {
-    let mut child = Command::new("/bin/zsh")
-        .args(["-lc", "npm install"])
-        .current_dir(&project_path)
+    let manager = detect_package_manager(&PathBuf::from(&project_path));
+    let (program, args) = install_argv(&manager);
+
+    let mut child = manager_command(&program, &args, &project_path)
         .env("FORCE_COLOR", "1")
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
-        .map_err(|e| format!("Failed to run npm install: {}", e))?;
+        .map_err(|e| format!("Failed to run {} install: {}", manager, e))?;
     
     let tab_id_clone_stdout = tab_id.clone();
     let tab_id_clone_stderr = tab_id.clone();
@@ -612,6 +1135,470 @@ async fn reload_browser_tab(port: u16) -> Result<(), String> {
     Ok(())
 }
 
+#[derive(Serialize, Clone)]
+pub struct DoctorReport {
+    #[serde(rename = "nodeVersion")]
+    node_version: Option<String>,
+    manager: String,
+    #[serde(rename = "managerVersion")]
+    manager_version: Option<String>,
+    #[serde(rename = "gitBranch")]
+    git_branch: Option<String>,
+    #[serde(rename = "enginesSatisfied")]
+    engines_satisfied: bool,
+    #[serde(rename = "nodeModulesStale")]
+    node_modules_stale: bool,
+    #[serde(rename = "portConflicts")]
+    port_conflicts: Vec<PortInfo>,
+}
+
+// Run `program --version` and capture its trimmed stdout, swallowing the
+// error if the tool simply isn't installed.
+fn command_version(program: &str) -> Option<String> {
+    Command::new(program)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+}
+
+// Pull the leading major version number out of a version string or semver
+// range, e.g. "v18.17.0" -> 18, ">=18.0.0" -> 18, "^18" -> 18.
+fn parse_major_version(text: &str) -> Option<u64> {
+    text.trim_start_matches(['v', '^', '~', '>', '<', '=', ' '])
+        .split('.')
+        .next()?
+        .parse()
+        .ok()
+}
+
+// Only checks the major version against `engines.node` - good enough to flag
+// "you're on Node 16 but this needs 18", without pulling in a full semver parser.
+fn engines_satisfied(pkg: &serde_json::Value, node_version: Option<&str>) -> bool {
+    let required = pkg.get("engines").and_then(|e| e.get("node")).and_then(|v| v.as_str());
+    match (required.and_then(parse_major_version), node_version.and_then(parse_major_version)) {
+        (Some(required_major), Some(actual_major)) => actual_major >= required_major,
+        _ => true,
+    }
+}
+
+// A project is "stale" if node_modules is missing, or the lockfile has been
+// touched more recently than node_modules (install hasn't caught up yet).
+fn node_modules_stale(project_path: &PathBuf, manager: &str) -> bool {
+    let node_modules = project_path.join("node_modules");
+    if !node_modules.exists() {
+        return true;
+    }
+
+    let lockfile_name = match manager {
+        "yarn" => "yarn.lock",
+        "pnpm" => "pnpm-lock.yaml",
+        "bun" => "bun.lockb",
+        _ => "package-lock.json",
+    };
+
+    let lock_mtime = fs::metadata(project_path.join(lockfile_name)).and_then(|m| m.modified()).ok();
+    let node_modules_mtime = fs::metadata(&node_modules).and_then(|m| m.modified()).ok();
+
+    match (lock_mtime, node_modules_mtime) {
+        (Some(lock), Some(installed)) => lock > installed,
+        _ => false,
+    }
+}
+
+// Pull explicit port numbers out of a script command, e.g. "vite --port 4000"
+// or "next dev -p 4000" or "PORT=4000 node server.js".
+fn extract_ports(script: &str) -> Vec<u16> {
+    let mut ports = Vec::new();
+    let tokens: Vec<&str> = script.split_whitespace().collect();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if let Some(value) = token.strip_prefix("--port=") {
+            if let Ok(port) = value.parse() {
+                ports.push(port);
+            }
+        } else if let Some(value) = token.strip_prefix("PORT=") {
+            if let Ok(port) = value.parse() {
+                ports.push(port);
+            }
+        } else if *token == "--port" || *token == "-p" {
+            if let Some(port) = tokens.get(i + 1).and_then(|v| v.parse().ok()) {
+                ports.push(port);
+            }
+        }
+    }
+
+    ports
+}
+
+// The set of ports this project's own dev/start/serve scripts actually bind
+// to, so project_doctor's port_conflicts only flags ports that matter to
+// this project rather than every dev port on the machine. Falls back to the
+// well-known default for whatever dev-server framework the project depends
+// on when no script spells the port out explicitly.
+fn project_dev_ports(pkg: &serde_json::Value) -> std::collections::HashSet<u16> {
+    let mut ports = std::collections::HashSet::new();
+
+    if let Some(scripts) = pkg.get("scripts").and_then(|v| v.as_object()) {
+        for (name, value) in scripts {
+            if !["dev", "start", "serve"].contains(&name.as_str()) {
+                continue;
+            }
+            if let Some(script) = value.as_str() {
+                ports.extend(extract_ports(script));
+            }
+        }
+    }
+
+    if ports.is_empty() {
+        let has_dep = |name: &str| {
+            ["dependencies", "devDependencies"].iter().any(|key| {
+                pkg.get(key)
+                    .and_then(|v| v.as_object())
+                    .map(|deps| deps.contains_key(name))
+                    .unwrap_or(false)
+            })
+        };
+
+        if has_dep("vite") {
+            ports.insert(5173);
+        }
+        if has_dep("next") || has_dep("react-scripts") || has_dep("nuxt") {
+            ports.insert(3000);
+        }
+        if has_dep("@angular/cli") {
+            ports.insert(4200);
+        }
+    }
+
+    ports
+}
+
+#[tauri::command]
+async fn project_doctor(project_path: String) -> Result<DoctorReport, String> {
+    let path = PathBuf::from(&project_path);
+
+    let node_version = command_version("node");
+    let manager = detect_package_manager(&path);
+    let manager_version = command_version(&manager);
+
+    let git_branch = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(&path)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+
+    let pkg: serde_json::Value = fs::read_to_string(path.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_else(|| serde_json::json!({}));
+
+    let engines_satisfied = engines_satisfied(&pkg, node_version.as_deref());
+    let node_modules_stale = node_modules_stale(&path, &manager);
+    let dev_ports = project_dev_ports(&pkg);
+    let port_conflicts = list_open_ports()
+        .await?
+        .into_iter()
+        .filter(|p| dev_ports.contains(&p.port))
+        .collect();
+
+    Ok(DoctorReport {
+        node_version,
+        manager,
+        manager_version,
+        git_branch,
+        engines_satisfied,
+        node_modules_stale,
+        port_conflicts,
+    })
+}
+
+#[derive(Serialize, Clone)]
+pub struct OutdatedPackage {
+    name: String,
+    current: Option<String>,
+    wanted: Option<String>,
+    latest: Option<String>,
+}
+
+// npm and pnpm both report `outdated --json` as a flat object keyed by
+// package name: { "<name>": { "current": ..., "wanted": ..., "latest": ... } }.
+fn parse_npm_style_outdated(json: &serde_json::Value) -> Vec<OutdatedPackage> {
+    json.as_object()
+        .map(|obj| {
+            obj.iter()
+                .map(|(name, info)| OutdatedPackage {
+                    name: name.clone(),
+                    current: info.get("current").and_then(|v| v.as_str()).map(String::from),
+                    wanted: info.get("wanted").and_then(|v| v.as_str()).map(String::from),
+                    latest: info.get("latest").and_then(|v| v.as_str()).map(String::from),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Yarn classic reports a table: { "data": { "head": [...], "body": [[name, current, wanted, latest, ...]] } }.
+// `data` here is already that inner object, pulled out of the NDJSON stream
+// by `parse_outdated`.
+fn parse_yarn_outdated(data: &serde_json::Value) -> Vec<OutdatedPackage> {
+    data.get("body")
+        .and_then(|b| b.as_array())
+        .map(|rows| {
+            rows.iter()
+                .filter_map(|row| {
+                    let cols = row.as_array()?;
+                    Some(OutdatedPackage {
+                        name: cols.first()?.as_str()?.to_string(),
+                        current: cols.get(1).and_then(|v| v.as_str()).map(String::from),
+                        wanted: cols.get(2).and_then(|v| v.as_str()).map(String::from),
+                        latest: cols.get(3).and_then(|v| v.as_str()).map(String::from),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_outdated(manager: &str, text: &str) -> Vec<OutdatedPackage> {
+    if manager == "yarn" {
+        // `yarn outdated --json` (classic) writes newline-delimited JSON
+        // objects, not one document - e.g. a line of {"type":"info",...}
+        // followed by the {"type":"table","data":{...}} line we actually
+        // want. Parsing the whole blob as one Value always fails (trailing
+        // data) and silently yields zero packages, so scan line by line.
+        return text
+            .lines()
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .find(|msg| msg.get("type").and_then(|t| t.as_str()) == Some("table"))
+            .and_then(|msg| msg.get("data").cloned())
+            .map(|data| parse_yarn_outdated(&data))
+            .unwrap_or_default();
+    }
+
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(text) else {
+        return Vec::new();
+    };
+    parse_npm_style_outdated(&json)
+}
+
+#[tauri::command]
+async fn check_outdated(app: AppHandle, project_path: String) -> Result<Vec<OutdatedPackage>, String> {
+    let manager = detect_package_manager(&PathBuf::from(&project_path));
+
+    let output = Command::new(&manager)
+        .args(["outdated", "--json"])
+        .current_dir(&project_path)
+        .output()
+        .map_err(|e| format!("Failed to run {} outdated: {}", manager, e))?;
+
+    // npm/yarn/pnpm all exit non-zero when outdated packages are found, so we
+    // parse stdout regardless of the exit status.
+    let packages = parse_outdated(&manager, &String::from_utf8_lossy(&output.stdout));
+
+    for package in &packages {
+        let _ = app.emit("package-outdated", package.clone());
+    }
+
+    Ok(packages)
+}
+
+#[derive(Serialize, Clone)]
+pub struct PluginInfo {
+    name: String,
+    commands: Vec<String>,
+}
+
+// Newline-delimited JSON-RPC messages a plugin can send back over stdout.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum PluginMessage {
+    Config {
+        #[serde(default)]
+        commands: Vec<String>,
+    },
+    Output {
+        stream: String,
+        data: String,
+    },
+    Exit {
+        #[serde(default)]
+        code: i32,
+    },
+}
+
+// Write one JSON-RPC request as a single line, e.g. { "method": "run", "params": {...} }.
+fn send_plugin_request(
+    stdin: &mut std::process::ChildStdin,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<(), String> {
+    use std::io::Write;
+    let request = serde_json::json!({ "method": method, "params": params });
+    writeln!(stdin, "{}", request).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn list_plugins() -> Result<Vec<PluginInfo>, String> {
+    use std::io::BufRead;
+
+    let mut infos = Vec::new();
+
+    for manifest in discover_plugin_manifests() {
+        let child = Command::new(&manifest.command)
+            .args(&manifest.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn();
+
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) => {
+                eprintln!("Failed to probe plugin '{}': {}", manifest.name, e);
+                continue;
+            }
+        };
+
+        let mut commands = Vec::new();
+        if let Some(mut stdin) = child.stdin.take() {
+            if send_plugin_request(&mut stdin, "config", serde_json::json!({})).is_ok() {
+                if let Some(stdout) = child.stdout.take() {
+                    // Read the reply on its own thread so a plugin that spawns
+                    // but never answers `config` can't wedge list_plugins (the
+                    // UI's discovery entrypoint) indefinitely.
+                    let (tx, rx) = std::sync::mpsc::channel();
+                    std::thread::spawn(move || {
+                        let mut line = String::new();
+                        let _ = std::io::BufReader::new(stdout).read_line(&mut line);
+                        let _ = tx.send(line);
+                    });
+
+                    if let Ok(line) = rx.recv_timeout(std::time::Duration::from_secs(3)) {
+                        if let Ok(PluginMessage::Config { commands: c }) = serde_json::from_str(line.trim()) {
+                            commands = c;
+                        }
+                    }
+                }
+            }
+        }
+
+        let _ = child.kill();
+        let _ = child.wait();
+
+        infos.push(PluginInfo { name: manifest.name, commands });
+    }
+
+    Ok(infos)
+}
+
+#[tauri::command]
+fn run_plugin(
+    app: AppHandle,
+    name: String,
+    project_path: String,
+    args: Vec<String>,
+    state: State<AppState>,
+) -> Result<bool, String> {
+    {
+        let plugins = state.plugins.lock().map_err(|e| e.to_string())?;
+        if plugins.contains_key(&name) {
+            return Err(format!("Plugin '{}' is already running", name));
+        }
+    }
+
+    let manifest = discover_plugin_manifests()
+        .into_iter()
+        .find(|m| m.name == name)
+        .ok_or_else(|| format!("Unknown plugin '{}'", name))?;
+
+    let mut child = Command::new(&manifest.command)
+        .args(&manifest.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start plugin '{}': {}", name, e))?;
+
+    let mut stdin = child.stdin.take().ok_or_else(|| "Plugin has no stdin".to_string())?;
+    send_plugin_request(&mut stdin, "config", serde_json::json!({}))?;
+    send_plugin_request(
+        &mut stdin,
+        "run",
+        serde_json::json!({ "project_path": project_path, "args": args }),
+    )?;
+
+    let script_name = format!("plugin:{}", name);
+
+    if let Some(stderr) = child.stderr.take() {
+        let app = app.clone();
+        let script = script_name.clone();
+        std::thread::spawn(move || {
+            use std::io::{BufRead, BufReader};
+            let reader = BufReader::new(stderr);
+            for line in reader.lines().flatten() {
+                let _ = app.emit("script-output", ScriptOutput {
+                    script: script.clone(),
+                    output_type: "stderr".to_string(),
+                    data: format!("{}\n", line),
+                    tab_id: script.clone(),
+                });
+            }
+        });
+    }
+
+    let stdout = child.stdout.take().ok_or_else(|| "Plugin has no stdout".to_string())?;
+
+    {
+        let mut plugins = state.plugins.lock().map_err(|e| e.to_string())?;
+        plugins.insert(name.clone(), child);
+    }
+
+    std::thread::spawn(move || {
+        use std::io::{BufRead, BufReader};
+        let reader = BufReader::new(stdout);
+        for line in reader.lines().flatten() {
+            match serde_json::from_str::<PluginMessage>(line.trim()) {
+                Ok(PluginMessage::Output { stream, data }) => {
+                    let _ = app.emit("script-output", ScriptOutput {
+                        script: script_name.clone(),
+                        output_type: stream,
+                        data,
+                        tab_id: script_name.clone(),
+                    });
+                }
+                Ok(PluginMessage::Exit { .. }) => break,
+                Ok(PluginMessage::Config { .. }) | Err(_) => {}
+            }
+        }
+
+        if let Some(state) = app.try_state::<AppState>() {
+            if let Ok(mut plugins) = state.plugins.lock() {
+                if let Some(mut child) = plugins.remove(&name) {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                }
+            }
+        }
+    });
+
+    Ok(true)
+}
+
+#[tauri::command]
+fn stop_plugin(name: String, state: State<AppState>) -> Result<bool, String> {
+    let mut plugins = state.plugins.lock().map_err(|e| e.to_string())?;
+    if let Some(mut child) = plugins.remove(&name) {
+        let _ = child.kill();
+        Ok(true)
+    } else {
+        Err(format!("Plugin '{}' is not running", name))
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -631,7 +1618,153 @@ pub fn run() {
             list_open_ports,
             kill_single_port,
             reload_browser_tab,
+            project_doctor,
+            check_outdated,
+            list_plugins,
+            run_plugin,
+            stop_plugin,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_package_lock_reads_version_and_registry() {
+        let content = r#"{
+            "packages": {
+                "": { "name": "root", "version": "1.0.0" },
+                "node_modules/lodash": {
+                    "version": "4.17.21",
+                    "resolved": "https://registry.npmjs.org/lodash/-/lodash-4.17.21.tgz"
+                },
+                "node_modules/@scope/pkg": {
+                    "version": "1.0.0",
+                    "resolved": "https://registry.npmjs.org/@scope/pkg/-/pkg-1.0.0.tgz"
+                }
+            }
+        }"#;
+
+        let resolved = parse_package_lock(content);
+
+        let lodash = resolved.get("lodash").expect("lodash entry");
+        assert_eq!(lodash.version, "4.17.21");
+        assert_eq!(lodash.registry.as_deref(), Some("https://registry.npmjs.org"));
+
+        let scoped = resolved.get("@scope/pkg").expect("scoped entry");
+        assert_eq!(scoped.version, "1.0.0");
+    }
+
+    #[test]
+    fn parse_yarn_lock_reads_version_and_registry_for_each_alias() {
+        let content = concat!(
+            "minimist@^1.2.0, minimist@^1.2.5:\n",
+            "  version \"1.2.8\"\n",
+            "  resolved \"https://registry.yarnpkg.com/minimist/-/minimist-1.2.8.tgz#abc\"\n",
+            "  integrity sha512-abc\n",
+        );
+
+        let resolved = parse_yarn_lock(content);
+
+        // Both comma-separated aliases collapse to the one "minimist" entry.
+        let minimist = resolved.get("minimist").expect("minimist entry");
+        assert_eq!(minimist.version, "1.2.8");
+        assert_eq!(minimist.registry.as_deref(), Some("https://registry.yarnpkg.com"));
+    }
+
+    #[test]
+    fn parse_pnpm_lock_reads_version_without_registry() {
+        let content = concat!(
+            "packages:\n",
+            "  /lodash@4.17.21:\n",
+            "    resolution: {integrity: sha512-abc}\n",
+            "  minimist@1.2.8:\n",
+            "    resolution: {integrity: sha512-def}\n",
+        );
+
+        let resolved = parse_pnpm_lock(content);
+
+        let lodash = resolved.get("lodash").expect("lodash entry");
+        assert_eq!(lodash.version, "4.17.21");
+        assert_eq!(lodash.registry, None);
+
+        let minimist = resolved.get("minimist").expect("minimist entry");
+        assert_eq!(minimist.version, "1.2.8");
+    }
+
+    #[test]
+    fn extract_registry_strips_path_from_tarball_url() {
+        assert_eq!(
+            extract_registry("https://registry.npmjs.org/lodash/-/lodash-4.17.21.tgz"),
+            Some("https://registry.npmjs.org".to_string())
+        );
+        assert_eq!(
+            extract_registry("https://registry.npmjs.org"),
+            Some("https://registry.npmjs.org".to_string())
+        );
+        assert_eq!(extract_registry("not-a-url"), None);
+    }
+
+    #[test]
+    fn parse_outdated_handles_npm_style_object() {
+        let text = r#"{"lodash":{"current":"4.17.20","wanted":"4.17.21","latest":"4.17.21"}}"#;
+        let packages = parse_outdated("npm", text);
+
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].name, "lodash");
+        assert_eq!(packages[0].current.as_deref(), Some("4.17.20"));
+        assert_eq!(packages[0].latest.as_deref(), Some("4.17.21"));
+    }
+
+    #[test]
+    fn parse_outdated_handles_yarn_ndjson_stream() {
+        let text = concat!(
+            "{\"type\":\"info\",\"data\":\"yarn info\"}\n",
+            "{\"type\":\"table\",\"data\":{\"head\":[\"Package\",\"Current\",\"Wanted\",\"Latest\"],",
+            "\"body\":[[\"lodash\",\"4.17.20\",\"4.17.21\",\"4.17.21\"]]}}\n",
+        );
+
+        let packages = parse_outdated("yarn", text);
+
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].name, "lodash");
+        assert_eq!(packages[0].wanted.as_deref(), Some("4.17.21"));
+    }
+
+    #[test]
+    fn parse_outdated_yarn_with_no_table_line_returns_empty() {
+        let text = "{\"type\":\"info\",\"data\":\"up to date\"}\n";
+        assert!(parse_outdated("yarn", text).is_empty());
+    }
+
+    #[test]
+    fn parse_major_version_handles_common_prefixes() {
+        assert_eq!(parse_major_version("v18.17.0"), Some(18));
+        assert_eq!(parse_major_version("^18"), Some(18));
+        assert_eq!(parse_major_version(">=18.0.0"), Some(18));
+        assert_eq!(parse_major_version(""), None);
+    }
+
+    #[test]
+    fn engines_satisfied_compares_major_versions() {
+        let pkg = serde_json::json!({ "engines": { "node": ">=18.0.0" } });
+        assert!(!engines_satisfied(&pkg, Some("v16.0.0")));
+        assert!(engines_satisfied(&pkg, Some("v18.5.0")));
+        assert!(engines_satisfied(&pkg, Some("v20.0.0")));
+
+        let no_engines = serde_json::json!({});
+        assert!(engines_satisfied(&no_engines, Some("v16.0.0")));
+    }
+
+    #[test]
+    fn extract_ports_reads_explicit_flags_and_env_var() {
+        assert_eq!(extract_ports("vite --port 4000"), vec![4000]);
+        assert_eq!(extract_ports("next dev -p 4000"), vec![4000]);
+        assert_eq!(extract_ports("PORT=5000 node server.js"), vec![5000]);
+        assert_eq!(extract_ports("--port=4000 vite"), vec![4000]);
+        assert!(extract_ports("react-scripts start").is_empty());
+    }
+}